@@ -0,0 +1,100 @@
+#![cfg(feature = "async")]
+//! Async variants of the write/verify paths, built on the tokio Parquet reader/writer
+//! so large generated files (or object-store-backed inputs) don't have to be loaded
+//! whole row-group-at-a-time.
+
+use crate::data_generator::AnalyticsData;
+use crate::parquet_writer::{apply_bloom_filters, validate_bloom_columns, BloomColumnConfig};
+use parquet::arrow::async_reader::ParquetRecordBatchStreamBuilder;
+use parquet::arrow::async_writer::AsyncArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, SeekFrom};
+
+/// Write analytics data to a Parquet file using the tokio-based async Arrow writer.
+pub async fn write_parquet_file_async(
+    data: &AnalyticsData,
+    filename: &str,
+    bloom_columns: &[BloomColumnConfig],
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Creating Arrow schema and record batch (async)...");
+    let record_batch = data.to_record_batch()?;
+    let schema = AnalyticsData::get_schema();
+
+    validate_bloom_columns(&schema, bloom_columns)?;
+
+    let builder = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::try_new(9).unwrap()))
+        .set_statistics_enabled(EnabledStatistics::Page)
+        .set_bloom_filter_enabled(false);
+    let props = apply_bloom_filters(builder, bloom_columns).build();
+
+    let file = File::create(filename).await?;
+    let mut writer = AsyncArrowWriter::try_new(file, Arc::new(schema), Some(props))?;
+    writer.write(&record_batch).await?;
+    writer.close().await?;
+
+    println!(
+        "✅ (async) Created {} with {} rows",
+        filename,
+        data.team_ids.len()
+    );
+
+    Ok(())
+}
+
+/// Verify Bloom filters in a Parquet file using the async reader, which only fetches
+/// the metadata up front and then, per column, the Bloom-filter byte range itself
+/// via a single ranged async read -- never a whole row group.
+///
+/// This fetches the raw bytes with `AsyncSeekExt`/`AsyncReadExt` on a second file
+/// handle rather than through a `parquet` async-reader bloom-filter API, since
+/// there's no manifest/lockfile here to confirm which such API the pinned
+/// `parquet` version exposes; `tokio::fs::File`'s seek/read are guaranteed
+/// stable. Decoding the fetched Sbbf bytes into a usable filter is out of scope
+/// for this check, which only confirms the byte range is fetchable.
+pub async fn verify_bloom_filter_async(filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Verifying Bloom filters in {} (async)...", filename);
+
+    let file = File::open(filename).await?;
+    let builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+    let metadata = builder.metadata().clone();
+
+    println!("Number of rows: {}", metadata.file_metadata().num_rows());
+    println!("Number of row groups: {}", metadata.num_row_groups());
+
+    let mut verify_file = File::open(filename).await?;
+
+    for rg_idx in 0..metadata.num_row_groups() {
+        println!("\nRow Group {}:", rg_idx);
+        let row_group = metadata.row_group(rg_idx);
+        for col_idx in 0..row_group.num_columns() {
+            let column = row_group.column(col_idx);
+            match (column.bloom_filter_offset(), column.bloom_filter_length()) {
+                (Some(offset), Some(length)) => {
+                    let mut bytes = vec![0u8; length as usize];
+                    verify_file.seek(SeekFrom::Start(offset as u64)).await?;
+                    verify_file.read_exact(&mut bytes).await?;
+                    println!(
+                        "  Column {} ({}): Bloom filter at offset {}, {} bytes -- fetched via a single ranged async read",
+                        col_idx,
+                        column.column_path(),
+                        offset,
+                        length
+                    );
+                }
+                _ => {
+                    println!(
+                        "  Column {} ({}): no Bloom filter",
+                        col_idx,
+                        column.column_path()
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use arrow::array::{Array, StringArray};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use rand::prelude::*;
+use std::collections::HashSet;
+use std::fs::File;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Measurements gathered while resolving a batch of `distinct_id` lookups
+/// against a single Parquet file.
+struct FileBenchReport {
+    rows_scanned: usize,
+    row_groups_total: usize,
+    row_groups_skipped: usize,
+    elapsed: Duration,
+    false_positives: usize,
+    negatives_tested: usize,
+    has_bloom_filter: bool,
+}
+
+/// Generate `queries` lookup keys (half sampled from real `distinct_id` values in
+/// `bloom_filename`, half random UUIDs guaranteed absent) and measure how much
+/// scanning each row-group Bloom filter saves versus a file with no filters.
+pub fn run_benchmark(prefix: &str, queries: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let bloom_filename = format!("{}_bloom.parquet", prefix);
+    let no_bloom_filename = format!("{}_no_bloom.parquet", prefix);
+
+    println!(
+        "🧪 Benchmarking Bloom-filter pruning with {} queries across {} and {}...",
+        queries, bloom_filename, no_bloom_filename
+    );
+
+    let sample_ids = sample_distinct_ids(&bloom_filename, queries)?;
+    let known_present: HashSet<String> = sample_ids.iter().cloned().collect();
+
+    let mut rng = thread_rng();
+    let query_values: Vec<String> = (0..queries)
+        .map(|i| {
+            if i % 2 == 0 && !sample_ids.is_empty() {
+                sample_ids.choose(&mut rng).unwrap().clone()
+            } else {
+                Uuid::new_v4().to_string()
+            }
+        })
+        .collect();
+
+    println!("\n📁 Scanning {} (WITH Bloom filters)...", bloom_filename);
+    let bloom_report = benchmark_file(&bloom_filename, &query_values, &known_present)?;
+
+    println!("\n📁 Scanning {} (WITHOUT Bloom filters)...", no_bloom_filename);
+    let no_bloom_report = benchmark_file(&no_bloom_filename, &query_values, &known_present)?;
+
+    println!("\n📊 Results ({} queries):", queries);
+    print_report("WITH Bloom filters", &bloom_report);
+    print_report("WITHOUT Bloom filters", &no_bloom_report);
+
+    let pruning_ratio = if bloom_report.row_groups_total > 0 {
+        bloom_report.row_groups_skipped as f64 / bloom_report.row_groups_total as f64
+    } else {
+        0.0
+    };
+    println!("\n🎯 Pruning ratio achieved: {:.1}% of row groups skipped", pruning_ratio * 100.0);
+
+    Ok(())
+}
+
+fn print_report(label: &str, report: &FileBenchReport) {
+    println!("  {}:", label);
+    println!("    Rows scanned: {}", report.rows_scanned);
+    println!(
+        "    Row groups skipped: {}/{}",
+        report.row_groups_skipped, report.row_groups_total
+    );
+    println!("    Wall-clock time: {:?}", report.elapsed);
+
+    if !report.has_bloom_filter {
+        // Without a filter every row group "survives" by construction, so a
+        // false-positive rate here would always read ~100% and mean nothing.
+        println!("    Observed false-positive rate: N/A (no Bloom filter on this file)");
+        return;
+    }
+
+    let fpp_observed = if report.negatives_tested > 0 {
+        report.false_positives as f64 / report.negatives_tested as f64
+    } else {
+        0.0
+    };
+    println!(
+        "    Observed false-positive rate: {:.2}% ({}/{} absent queries)",
+        fpp_observed * 100.0,
+        report.false_positives,
+        report.negatives_tested
+    );
+}
+
+/// Read up to `limit` real `distinct_id` values out of `filename` to use as
+/// queries that are guaranteed to be present.
+fn sample_distinct_ids(filename: &str, limit: usize) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let file = File::open(filename)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+    let reader = builder.build()?;
+
+    let mut ids = Vec::new();
+    for batch_result in reader {
+        let batch = batch_result?;
+        if let Some(distinct_id_array) = batch
+            .column_by_name("distinct_id")
+            .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+        {
+            for value in distinct_id_array.iter().flatten() {
+                ids.push(value.to_string());
+                if ids.len() >= limit {
+                    return Ok(ids);
+                }
+            }
+        }
+    }
+    Ok(ids)
+}
+
+fn benchmark_file(
+    filename: &str,
+    queries: &[String],
+    known_present: &HashSet<String>,
+) -> Result<FileBenchReport, Box<dyn std::error::Error>> {
+    let file = File::open(filename)?;
+    let reader = SerializedFileReader::new(file)?;
+    let metadata = reader.metadata();
+    let schema = metadata.file_metadata().schema_descr();
+    let column_idx = (0..schema.num_columns())
+        .find(|&i| schema.column(i).path().string() == "distinct_id")
+        .ok_or("distinct_id column not found in schema")?;
+
+    let start = Instant::now();
+    let mut rows_scanned = 0usize;
+    let mut row_groups_skipped = 0usize;
+    let mut row_groups_total = 0usize;
+    let mut false_positives = 0usize;
+    let mut negatives_tested = 0usize;
+    let mut has_bloom_filter = false;
+
+    for value in queries {
+        let is_known_present = known_present.contains(value);
+        if !is_known_present {
+            negatives_tested += 1;
+        }
+
+        let mut surviving = Vec::new();
+        for rg_idx in 0..metadata.num_row_groups() {
+            row_groups_total += 1;
+            let row_group_reader = reader.get_row_group(rg_idx)?;
+            let maybe_present = match row_group_reader.get_column_bloom_filter(column_idx) {
+                Some(bloom_filter) => {
+                    has_bloom_filter = true;
+                    bloom_filter.check(value.as_bytes())
+                }
+                None => true, // no filter recorded, must scan
+            };
+            if maybe_present {
+                surviving.push(rg_idx);
+            } else {
+                row_groups_skipped += 1;
+            }
+        }
+
+        if !is_known_present && !surviving.is_empty() {
+            false_positives += 1;
+        }
+
+        if surviving.is_empty() {
+            continue;
+        }
+
+        let file = File::open(filename)?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?.with_row_groups(surviving);
+        let batch_reader = builder.build()?;
+        for batch_result in batch_reader {
+            let batch = batch_result?;
+            rows_scanned += batch.num_rows();
+        }
+    }
+
+    Ok(FileBenchReport {
+        rows_scanned,
+        row_groups_total,
+        row_groups_skipped,
+        elapsed: start.elapsed(),
+        false_positives,
+        negatives_tested,
+        has_bloom_filter,
+    })
+}
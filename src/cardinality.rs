@@ -0,0 +1,99 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Exact cardinality counter for bounded, enum-like domains where every
+/// possible value has a known ordinal (`team_id`'s `1..=10`, `event`'s five
+/// fixed strings): OR a dedicated bit per ordinal into a 64-bit bitset
+/// instead of holding a `HashSet`, the same bit-seen trick polars uses for
+/// its fast distinct-count approximation on bounded domains.
+pub struct BitsetCardinality {
+    bits: u64,
+}
+
+impl BitsetCardinality {
+    pub fn new() -> Self {
+        BitsetCardinality { bits: 0 }
+    }
+
+    /// Mark the value at `ordinal` (its index within the known, bounded
+    /// domain) as seen. `ordinal` must be `< 64`.
+    pub fn observe(&mut self, ordinal: usize) {
+        debug_assert!(ordinal < 64, "BitsetCardinality domains must fit in 64 bits, got ordinal {}", ordinal);
+        self.bits |= 1 << ordinal;
+    }
+
+    pub fn estimate(&self) -> u64 {
+        self.bits.count_ones() as u64
+    }
+}
+
+impl Default for BitsetCardinality {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// HyperLogLog cardinality estimator for high-cardinality columns
+/// (`distinct_id`, `properties`) where holding a full `HashSet` in memory isn't
+/// worth it. `p` controls the register count (`2^p`) and therefore accuracy;
+/// 14 (16384 registers, ~0.8% standard error) is a reasonable default.
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+    p: u32,
+}
+
+impl HyperLogLog {
+    pub fn new(p: u32) -> Self {
+        HyperLogLog {
+            registers: vec![0u8; 1 << p],
+            p,
+        }
+    }
+
+    pub fn observe<T: Hash>(&mut self, value: T) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // The top `p` bits pick the register.
+        let index = hash.checked_shr(64 - self.p).unwrap_or(0) as usize;
+        // The remaining bits, past the index, feed the leading-zero-run count.
+        let rest = hash << self.p;
+        let rho = ((rest.leading_zeros() + 1) as u8).min(64 - self.p as u8);
+
+        if rho > self.registers[index] {
+            self.registers[index] = rho;
+        }
+    }
+
+    /// Standard HyperLogLog harmonic-mean estimate with the small/large-range
+    /// corrections from the original Flajolet et al. paper.
+    pub fn estimate(&self) -> u64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw_estimate = alpha * m * m / sum;
+
+        let two_32 = (1u64 << 32) as f64;
+        let estimate = if raw_estimate <= 2.5 * m {
+            let zero_registers = self.registers.iter().filter(|&&r| r == 0).count();
+            if zero_registers != 0 {
+                m * (m / zero_registers as f64).ln()
+            } else {
+                raw_estimate
+            }
+        } else if raw_estimate <= two_32 / 30.0 {
+            raw_estimate
+        } else {
+            -two_32 * (1.0 - raw_estimate / two_32).ln()
+        };
+
+        estimate.round().max(0.0) as u64
+    }
+}
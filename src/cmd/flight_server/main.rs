@@ -0,0 +1,141 @@
+// Reuses `AnalyticsData` so the Flight server's schema and generated rows
+// never drift from what `generate_sample_data` and `map_parquet` already
+// agree on.
+#[allow(dead_code)]
+#[path = "../../data_generator.rs"]
+mod data_generator;
+
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PutResult, SchemaAsIpc, SchemaResult, Ticket,
+};
+use data_generator::{generate_sample_data, AnalyticsData};
+use futures::stream::{self, BoxStream};
+use futures::TryStreamExt;
+use std::env;
+use tonic::{transport::Server, Request, Response, Status, Streaming};
+
+/// Ticket payload: which dataset a `DoGet` call should stream back.
+#[derive(serde::Deserialize)]
+struct DatasetTicket {
+    num_rows: usize,
+    seed: u64,
+}
+
+/// Serves generated analytics batches over Arrow Flight, so downstream query
+/// engines can read live-generated data without a file round-trip.
+#[derive(Default)]
+struct AnalyticsFlightService;
+
+#[tonic::async_trait]
+impl FlightService for AnalyticsFlightService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake is not required by this demo server"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        Ok(Response::new(Box::pin(stream::empty())))
+    }
+
+    async fn get_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        Err(Status::unimplemented("use GetSchema + DoGet with an explicit ticket"))
+    }
+
+    async fn get_schema(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let schema = AnalyticsData::get_schema();
+        let options = arrow::ipc::writer::IpcWriteOptions::default();
+        let schema_as_ipc = SchemaAsIpc::new(&schema, &options);
+        Ok(Response::new(schema_as_ipc.try_into().map_err(|e| {
+            Status::internal(format!("failed to encode schema: {}", e))
+        })?))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let ticket = request.into_inner();
+        let dataset: DatasetTicket = serde_json::from_slice(&ticket.ticket)
+            .map_err(|e| Status::invalid_argument(format!("invalid ticket: {}", e)))?;
+
+        let batch = generate_sample_data(dataset.num_rows, dataset.seed)
+            .to_record_batch()
+            .map_err(|e| Status::internal(format!("failed to generate batch: {}", e)))?;
+
+        let flight_data_stream = FlightDataEncoderBuilder::new()
+            .build(stream::iter(vec![Ok(batch)]))
+            .map_err(|e| Status::internal(e.to_string()));
+
+        Ok(Response::new(Box::pin(flight_data_stream)))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("this server only serves generated data, it has no ingest path"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("no custom actions are defined"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(Box::pin(stream::empty())))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do_exchange is not supported"))
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().collect();
+    let addr = args
+        .get(1)
+        .map(|s| s.as_str())
+        .unwrap_or("127.0.0.1:50051")
+        .parse()?;
+
+    println!("🚀 Serving analytics batches over Arrow Flight at {}", addr);
+    println!("   DoGet ticket format: {{\"num_rows\": N, \"seed\": S}}");
+
+    Server::builder()
+        .add_service(FlightServiceServer::new(AnalyticsFlightService))
+        .serve(addr)
+        .await?;
+
+    Ok(())
+}
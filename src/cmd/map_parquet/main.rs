@@ -1,18 +1,158 @@
+// Only `AnalyticsData::get_schema` is needed here; the rest of this module's
+// API (generation, sorting, batch conversion) isn't used by this binary.
+#[allow(dead_code)]
+#[path = "../../data_generator.rs"]
+mod data_generator;
+#[path = "../../stats_converter.rs"]
+mod stats_converter;
+
 use human_bytes::human_bytes;
+use parquet::file::page_index::index::Index;
 use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::serialized_reader::ReadOptionsBuilder;
 use parquet::file::statistics::Statistics;
+use stats_converter::column_statistics;
+use std::collections::HashMap;
 use std::env;
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 
+/// Format one data page's min/max/null-count from a column's `ColumnIndex`, for
+/// whichever physical type the page index was built over.
+fn format_page_summary(index: &Index, page_no: usize) -> String {
+    macro_rules! page_line {
+        ($native:expr) => {{
+            let page = &$native.indexes[page_no];
+            format!(
+                "min: {:?}, max: {:?}, nulls: {}",
+                page.min,
+                page.max,
+                page.null_count.unwrap_or(0)
+            )
+        }};
+    }
+
+    match index {
+        Index::NONE => "no page index".to_string(),
+        Index::BOOLEAN(native) => page_line!(native),
+        Index::INT32(native) => page_line!(native),
+        Index::INT64(native) => page_line!(native),
+        Index::FLOAT(native) => page_line!(native),
+        Index::DOUBLE(native) => page_line!(native),
+        Index::BYTE_ARRAY(native) => page_line!(native),
+        Index::FIXED_LEN_BYTE_ARRAY(native) => page_line!(native),
+        _ => "unsupported page index type".to_string(),
+    }
+}
+
+fn boundary_order(index: &Index) -> String {
+    match index {
+        Index::NONE => "n/a".to_string(),
+        Index::BOOLEAN(native) => format!("{:?}", native.boundary_order),
+        Index::INT32(native) => format!("{:?}", native.boundary_order),
+        Index::INT64(native) => format!("{:?}", native.boundary_order),
+        Index::FLOAT(native) => format!("{:?}", native.boundary_order),
+        Index::DOUBLE(native) => format!("{:?}", native.boundary_order),
+        Index::BYTE_ARRAY(native) => format!("{:?}", native.boundary_order),
+        Index::FIXED_LEN_BYTE_ARRAY(native) => format!("{:?}", native.boundary_order),
+        _ => "n/a".to_string(),
+    }
+}
+
+fn num_pages(index: &Index) -> usize {
+    match index {
+        Index::NONE => 0,
+        Index::BOOLEAN(native) => native.indexes.len(),
+        Index::INT32(native) => native.indexes.len(),
+        Index::INT64(native) => native.indexes.len(),
+        Index::FLOAT(native) => native.indexes.len(),
+        Index::DOUBLE(native) => native.indexes.len(),
+        Index::BYTE_ARRAY(native) => native.indexes.len(),
+        Index::FIXED_LEN_BYTE_ARRAY(native) => native.indexes.len(),
+        _ => 0,
+    }
+}
+
+/// Check a `distinct_id` value against each row group's Bloom filter and report
+/// which row groups could contain it and which are provably pruned.
+fn probe(path: &Path, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path)?;
+    let reader = SerializedFileReader::new(file)?;
+    let metadata = reader.metadata();
+
+    let row_group = metadata.row_group(0);
+    let column_idx = (0..row_group.num_columns())
+        .find(|&i| row_group.column(i).column_path().string() == "distinct_id")
+        .ok_or("distinct_id column not found in schema")?;
+
+    println!("Probing {:?} for distinct_id = '{}'", path, value);
+    for rg_idx in 0..metadata.num_row_groups() {
+        let row_group_reader = reader.get_row_group(rg_idx)?;
+        let verdict = match row_group_reader.get_column_bloom_filter(column_idx) {
+            Some(bloom_filter) if bloom_filter.check(value.as_bytes()) => "maybe present",
+            Some(_) => "❌ pruned (provably absent)",
+            None => "no Bloom filter, must scan",
+        };
+        println!("  Row group {}: {}", rg_idx, verdict);
+    }
+
+    Ok(())
+}
+
 fn format_bytes(size: u64) -> String {
     human_bytes(size as f64)
 }
 
+/// Format the row-group-`rg_idx` entry of a [`stats_converter::RowGroupStatistics`]
+/// the same way [`format_statistic`] renders the raw `Statistics` enum, for
+/// columns covered by [`data_generator::AnalyticsData::get_schema`].
+fn format_converted_statistic(stats: &stats_converter::RowGroupStatistics, rg_idx: usize) -> String {
+    use arrow::array::{Array, Int64Array, StringArray, TimestampNanosecondArray};
+
+    let null_count = stats.null_count.value(rg_idx);
+
+    if stats.min.is_null(rg_idx) || stats.max.is_null(rg_idx) {
+        return if null_count > 0 {
+            format!(" | Stats(nulls: {})", null_count)
+        } else {
+            " | Stats(no min/max)".to_string()
+        };
+    }
+
+    let (min_val, max_val) = match stats.min.data_type() {
+        arrow::datatypes::DataType::Int64 => {
+            let min = stats.min.as_any().downcast_ref::<Int64Array>().unwrap();
+            let max = stats.max.as_any().downcast_ref::<Int64Array>().unwrap();
+            (min.value(rg_idx).to_string(), max.value(rg_idx).to_string())
+        }
+        arrow::datatypes::DataType::Timestamp(_, _) => {
+            let min = stats.min.as_any().downcast_ref::<TimestampNanosecondArray>().unwrap();
+            let max = stats.max.as_any().downcast_ref::<TimestampNanosecondArray>().unwrap();
+            (min.value(rg_idx).to_string(), max.value(rg_idx).to_string())
+        }
+        arrow::datatypes::DataType::Utf8 => {
+            let min = stats.min.as_any().downcast_ref::<StringArray>().unwrap();
+            let max = stats.max.as_any().downcast_ref::<StringArray>().unwrap();
+            let (min_str, max_str) = (min.value(rg_idx), max.value(rg_idx));
+            let display = |s: &str| {
+                if s.len() > 20 {
+                    format!("{}...", &s[..17])
+                } else {
+                    format!("'{}'", s)
+                }
+            };
+            (display(min_str), display(max_str))
+        }
+        other => (format!("{:?}", other), format!("{:?}", other)),
+    };
+
+    format!(" | Stats(min: {}, max: {}, nulls: {})", min_val, max_val, null_count)
+}
+
 fn format_statistic(stat: &Statistics) -> String {
     let null_count = stat.null_count_opt().unwrap_or(0);
-    
+
     if stat.min_is_exact() && stat.max_is_exact() {
         let (min_val, max_val) = match stat {
             Statistics::Boolean(s) => {
@@ -39,26 +179,13 @@ fn format_statistic(stat: &Statistics) -> String {
                     _ => ("N/A".to_string(), "N/A".to_string())
                 }
             }
-            Statistics::Int64(s) => {
-                match (s.min_bytes_opt(), s.max_bytes_opt()) {
-                    (Some(min_bytes), Some(max_bytes)) => {
-                        let min_val = if min_bytes.len() >= 8 {
-                            i64::from_le_bytes([
-                                min_bytes[0], min_bytes[1], min_bytes[2], min_bytes[3],
-                                min_bytes[4], min_bytes[5], min_bytes[6], min_bytes[7],
-                            ])
-                        } else { 0 };
-                        let max_val = if max_bytes.len() >= 8 {
-                            i64::from_le_bytes([
-                                max_bytes[0], max_bytes[1], max_bytes[2], max_bytes[3],
-                                max_bytes[4], max_bytes[5], max_bytes[6], max_bytes[7],
-                            ])
-                        } else { 0 };
-                        (min_val.to_string(), max_val.to_string())
-                    }
-                    _ => ("N/A".to_string(), "N/A".to_string())
-                }
-            }
+            // Int64 covers `team_id` and `timestamp` in this tool's own files;
+            // those go through `format_converted_statistic` instead, so this
+            // arm only fires for arbitrary external Parquet files.
+            Statistics::Int64(s) => (
+                s.min_opt().map(|v| v.to_string()).unwrap_or_else(|| "N/A".to_string()),
+                s.max_opt().map(|v| v.to_string()).unwrap_or_else(|| "N/A".to_string()),
+            ),
             Statistics::Float(s) => {
                 match (s.min_bytes_opt(), s.max_bytes_opt()) {
                     (Some(min_bytes), Some(max_bytes)) => {
@@ -150,13 +277,17 @@ fn format_statistic(stat: &Statistics) -> String {
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
-        println!("Usage: map_parquet <path_to_your_file.parquet>");
+        println!("Usage: map_parquet <path_to_your_file.parquet> [probe <distinct_id>]");
         return Ok(());
     }
 
     let file_path = &args[1];
     let path = Path::new(file_path);
 
+    if args.len() >= 4 && args[2] == "probe" {
+        return probe(path, &args[3]);
+    }
+
     let mut file = File::open(&path)?;
 
     let file_size = file.metadata()?.len();
@@ -173,9 +304,42 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // We need to rewind the file to the beginning so that the reader can read it from the start.
     file.seek(SeekFrom::Start(0))?;
 
-    let reader = SerializedFileReader::new(file)?;
+    let options = ReadOptionsBuilder::new().with_page_index().build();
+    let reader = SerializedFileReader::new_with_options(file, options)?;
     let metadata = reader.metadata();
 
+    // distinct_count isn't a field the writer can set via the high-level Arrow
+    // API, so estimates are stashed in file key/value metadata as
+    // "distinct_count:<column>" instead; look them up once up front.
+    let distinct_counts: std::collections::HashMap<&str, &str> = metadata
+        .file_metadata()
+        .key_value_metadata()
+        .map(|kvs| {
+            kvs.iter()
+                .filter_map(|kv| {
+                    kv.key
+                        .strip_prefix("distinct_count:")
+                        .zip(kv.value.as_deref())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // For columns matching `AnalyticsData::get_schema`, materialize typed
+    // per-row-group stats up front instead of decoding each column chunk's
+    // raw min/max bytes by hand; arbitrary (non-AnalyticsData) files fall
+    // back to `format_statistic` below.
+    let converted_stats: HashMap<String, stats_converter::RowGroupStatistics> =
+        data_generator::AnalyticsData::get_schema()
+            .fields()
+            .iter()
+            .filter_map(|field| {
+                column_statistics(&reader, field.name())
+                    .ok()
+                    .map(|stats| (field.name().clone(), stats))
+            })
+            .collect();
+
     println!(
         "Physical Layout Tree for: {} (Total Size: {})",
         path.file_name().and_then(|s| s.to_str()).unwrap_or(""),
@@ -212,14 +376,56 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let uncompressed_size_str = format_bytes(column.uncompressed_size() as u64);
             let compressed_size_str = format_bytes(column.compressed_size() as u64);
 
-            let stats_str = if let Some(stats) = column.statistics() {
+            let stats_str = if let Some(converted) = converted_stats.get(column.column_path().string().as_str()) {
+                format_converted_statistic(converted, rg_idx)
+            } else if let Some(stats) = column.statistics() {
                 format_statistic(stats)
             } else {
                 "".to_string()
             };
 
+            let bloom_str = match (column.bloom_filter_offset(), column.bloom_filter_length()) {
+                (Some(offset), Some(length)) => {
+                    format!(" | Bloom filter @ offset {} ({} bytes)", offset, length)
+                }
+                _ => "".to_string(),
+            };
+
+            // SizeStatistics gives the true decoded size of variable-length data,
+            // which matters most for the `properties` JSON column; chunks with no
+            // recorded value (fixed-width columns, or files written before the
+            // feature existed) fall back to the on-disk uncompressed size.
+            let size_stats_str = match column.unencoded_byte_array_data_bytes() {
+                Some(unencoded_bytes) => format!(
+                    " | Unencoded size: {}",
+                    format_bytes(unencoded_bytes as u64)
+                ),
+                None => format!(" | Unencoded size: N/A (no byte-array SizeStatistics, falling back to {})", uncompressed_size_str),
+            };
+
+            // The other half of SizeStatistics: rep/def level histograms. This
+            // tool's own schema has no repeated or optional fields, so its max
+            // levels are 0 and no histograms are recorded; the segment is only
+            // printed for files that do have nested/nullable columns.
+            let level_histogram_str = match (
+                column.repetition_level_histogram(),
+                column.definition_level_histogram(),
+            ) {
+                (None, None) => "".to_string(),
+                (rep, def) => format!(
+                    " | Level histograms(rep: {:?}, def: {:?})",
+                    rep.map(|h| h.values()),
+                    def.map(|h| h.values()),
+                ),
+            };
+
+            let distinct_count_str = distinct_counts
+                .get(column.column_path().string().as_str())
+                .map(|count| format!(" | Distinct count (est.): {}", count))
+                .unwrap_or_default();
+
             let chunk_details = format!(
-                "Column '{}' ({:?}, {:?}) @ offset {} | Size: {} -> {} ({} values){}",
+                "Column '{}' ({:?}, {:?}) @ offset {} | Size: {} -> {} ({} values){}{}{}{}{}",
                 column.column_path(),
                 column.column_type(),
                 column.compression(),
@@ -227,12 +433,44 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 uncompressed_size_str,
                 compressed_size_str,
                 column.num_values(),
-                stats_str
+                stats_str,
+                bloom_str,
+                size_stats_str,
+                level_histogram_str,
+                distinct_count_str
             );
             println!(
                 "{}{} 📊 Column Chunk: {}",
                 rg_cont_prefix, col_prefix, chunk_details
             );
+
+            let page_prefix = format!("{}{}   ", rg_cont_prefix, if is_last_col { " " } else { "│" });
+            if let (Some(column_indexes), Some(offset_indexes)) =
+                (metadata.column_index(), metadata.offset_index())
+            {
+                let col_index = &column_indexes[rg_idx][col_idx];
+                let off_index = &offset_indexes[rg_idx][col_idx];
+                let pages = num_pages(col_index);
+                if pages > 0 {
+                    println!(
+                        "{}└── 📑 Page Index ({} pages, boundary order: {})",
+                        page_prefix,
+                        pages,
+                        boundary_order(col_index)
+                    );
+                    for (page_no, location) in off_index.page_locations.iter().enumerate() {
+                        println!(
+                            "{}    Page {}: {} @ offset {} ({} bytes compressed, first row {})",
+                            page_prefix,
+                            page_no,
+                            format_page_summary(col_index, page_no),
+                            location.offset,
+                            location.compressed_page_size,
+                            location.first_row_index
+                        );
+                    }
+                }
+            }
         }
     }
     println!();
@@ -1,14 +1,37 @@
+#[cfg(feature = "async")]
+mod async_io;
+mod benchmark;
 mod bloom_test;
+mod cardinality;
 mod data_generator;
 mod parquet_writer;
+mod probe;
+mod rewrite;
+mod stats_converter;
+mod topic_index;
 mod verify_bloom;
 
+#[cfg(feature = "async")]
+use async_io::{verify_bloom_filter_async, write_parquet_file_async};
+use benchmark::run_benchmark;
 use bloom_test::test_bloom_filter_functionality;
 use clap::{Parser, Subcommand};
 use data_generator::generate_sample_data;
-use parquet_writer::{write_parquet_file, BloomFilterMode};
+use parquet_writer::{write_parquet_file, BloomColumnConfig};
+use probe::probe_distinct_id;
+use rewrite::{parse_compression, parse_statistics, rewrite_parquet_file};
 use verify_bloom::verify_bloom_filter;
 
+/// Bloom columns to use when the user didn't pass `--bloom-column`, preserving the
+/// tool's original default of a single filter over `distinct_id`.
+fn default_bloom_columns(rows: usize) -> Vec<BloomColumnConfig> {
+    vec![BloomColumnConfig {
+        column: "distinct_id".to_string(),
+        ndv: rows as u64,
+        fpp: 0.01,
+    }]
+}
+
 #[derive(Parser)]
 #[command(name = "parquet-bloom-writer")]
 #[command(about = "A CLI tool for creating and verifying Parquet files with Bloom filters")]
@@ -28,6 +51,15 @@ enum Commands {
         /// Number of rows to generate (default: 1000)
         #[arg(short, long, default_value = "1000")]
         rows: usize,
+
+        /// Enable a Bloom filter on a column, as NAME:NDV:FPP (repeatable).
+        /// Defaults to a single filter over `distinct_id` if omitted.
+        #[arg(long = "bloom-column")]
+        bloom_columns: Vec<BloomColumnConfig>,
+
+        /// Build a fixed-size Bloom index over `properties` keys/values into file metadata
+        #[arg(long)]
+        topic_index: bool,
     },
 
     /// Verify Bloom filters in an existing Parquet file
@@ -44,6 +76,79 @@ enum Commands {
         filename: String,
     },
 
+    /// Probe a file for a distinct_id, pruning row groups via their Bloom filters first
+    Probe {
+        /// Input filename to probe
+        #[arg(short, long, default_value = "events_with_bloom.parquet")]
+        filename: String,
+
+        /// distinct_id value to look up
+        #[arg(short, long)]
+        value: Option<String>,
+
+        /// Check whether the file's topic Bloom index has ever seen this `properties` key
+        #[arg(long)]
+        has_property: Option<String>,
+    },
+
+    /// Re-encode an existing Parquet file with new compression/statistics/Bloom settings
+    Rewrite {
+        /// Input Parquet file to re-encode
+        #[arg(short, long)]
+        input: String,
+
+        /// Output filename for the rewritten file
+        #[arg(short, long)]
+        output: String,
+
+        /// Compression codec: uncompressed, snappy, gzip, lz4, zstd (default: zstd)
+        #[arg(short, long, default_value = "zstd")]
+        compression: String,
+
+        /// Statistics level: none, chunk, page (default: page)
+        #[arg(long, default_value = "page")]
+        statistics: String,
+
+        /// Enable a Bloom filter on a column, as NAME:NDV:FPP (repeatable)
+        #[arg(long = "bloom-column")]
+        bloom_columns: Vec<BloomColumnConfig>,
+    },
+
+    /// Measure Bloom-filter pruning effectiveness across a {prefix}_bloom/_no_bloom pair
+    Benchmark {
+        /// Filename prefix shared by the {prefix}_bloom.parquet/{prefix}_no_bloom.parquet pair
+        #[arg(short, long, default_value = "events")]
+        prefix: String,
+
+        /// Number of lookup queries to run (default: 100)
+        #[arg(short, long, default_value = "100")]
+        queries: usize,
+    },
+
+    /// Generate a Parquet file using the async tokio-based writer
+    #[cfg(feature = "async")]
+    GenerateAsync {
+        /// Output filename (default: events_with_bloom.parquet)
+        #[arg(short, long, default_value = "events_with_bloom.parquet")]
+        filename: String,
+
+        /// Number of rows to generate (default: 1000)
+        #[arg(short, long, default_value = "1000")]
+        rows: usize,
+
+        /// Enable a Bloom filter on a column, as NAME:NDV:FPP (repeatable)
+        #[arg(long = "bloom-column")]
+        bloom_columns: Vec<BloomColumnConfig>,
+    },
+
+    /// Verify Bloom filters using the async reader, fetching only metadata and filter ranges
+    #[cfg(feature = "async")]
+    VerifyAsync {
+        /// Input filename to verify
+        #[arg(short, long, default_value = "events_with_bloom.parquet")]
+        filename: String,
+    },
+
     /// Generate BOTH Parquet files (with and without Bloom filters) using identical data
     GenerateBoth {
         /// Base filename prefix (will create {prefix}_bloom.parquet and {prefix}_no_bloom.parquet)
@@ -57,6 +162,11 @@ enum Commands {
         /// Seed for reproducible data generation (default: 42)
         #[arg(short, long, default_value = "42")]
         seed: u64,
+
+        /// Enable a Bloom filter on a column, as NAME:NDV:FPP (repeatable), applied
+        /// only to the `_bloom` file. Defaults to a single filter over `distinct_id`.
+        #[arg(long = "bloom-column")]
+        bloom_columns: Vec<BloomColumnConfig>,
     },
 }
 
@@ -64,10 +174,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     match &cli.command {
-        Commands::Generate { filename, rows } => {
+        Commands::Generate { filename, rows, bloom_columns, topic_index } => {
             // Generate data with a default seed for backwards compatibility
             let data = generate_sample_data(*rows, 42);
-            write_parquet_file(&data, filename, BloomFilterMode::Enabled)?;
+            let bloom_columns = if bloom_columns.is_empty() {
+                default_bloom_columns(*rows)
+            } else {
+                bloom_columns.clone()
+            };
+            write_parquet_file(&data, filename, &bloom_columns, *topic_index)?;
         }
         Commands::Verify { filename } => {
             verify_bloom_filter(filename)?;
@@ -75,18 +190,63 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Test { filename } => {
             test_bloom_filter_functionality(filename)?;
         }
-        Commands::GenerateBoth { prefix, rows, seed } => {
+        Commands::Probe { filename, value, has_property } => {
+            if value.is_none() && has_property.is_none() {
+                return Err("probe requires --value or --has-property (nothing to probe)".into());
+            }
+            if let Some(key) = has_property {
+                probe::check_has_property(filename, key)?;
+            }
+            if let Some(value) = value {
+                probe_distinct_id(filename, value)?;
+            }
+        }
+        Commands::Rewrite {
+            input,
+            output,
+            compression,
+            statistics,
+            bloom_columns,
+        } => {
+            let compression = parse_compression(compression)?;
+            let statistics = parse_statistics(statistics)?;
+            rewrite_parquet_file(input, output, compression, statistics, bloom_columns)?;
+        }
+        Commands::Benchmark { prefix, queries } => {
+            run_benchmark(prefix, *queries)?;
+        }
+        #[cfg(feature = "async")]
+        Commands::GenerateAsync { filename, rows, bloom_columns } => {
+            let data = generate_sample_data(*rows, 42);
+            let bloom_columns = if bloom_columns.is_empty() {
+                default_bloom_columns(*rows)
+            } else {
+                bloom_columns.clone()
+            };
+            tokio::runtime::Runtime::new()?
+                .block_on(write_parquet_file_async(&data, filename, &bloom_columns))?;
+        }
+        #[cfg(feature = "async")]
+        Commands::VerifyAsync { filename } => {
+            tokio::runtime::Runtime::new()?.block_on(verify_bloom_filter_async(filename))?;
+        }
+        Commands::GenerateBoth { prefix, rows, seed, bloom_columns } => {
             println!("🔄 Generating identical data for both files...");
             let data = generate_sample_data(*rows, *seed);
+            let bloom_columns = if bloom_columns.is_empty() {
+                default_bloom_columns(*rows)
+            } else {
+                bloom_columns.clone()
+            };
 
             let bloom_filename = format!("{}_bloom.parquet", prefix);
             let no_bloom_filename = format!("{}_no_bloom.parquet", prefix);
 
             println!("\n📁 Writing file WITH Bloom filters...");
-            write_parquet_file(&data, &bloom_filename, BloomFilterMode::Enabled)?;
+            write_parquet_file(&data, &bloom_filename, &bloom_columns, false)?;
 
             println!("\n📁 Writing file WITHOUT Bloom filters...");
-            write_parquet_file(&data, &no_bloom_filename, BloomFilterMode::Disabled)?;
+            write_parquet_file(&data, &no_bloom_filename, &[], false)?;
 
             println!("\n✅ Successfully created both files:");
             println!("  🌸 {} (WITH Bloom filters)", bloom_filename);
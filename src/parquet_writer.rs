@@ -1,75 +1,240 @@
+use crate::cardinality::{BitsetCardinality, HyperLogLog};
 use crate::data_generator::AnalyticsData;
+use crate::topic_index::TopicIndex;
+use arrow::datatypes::DataType;
 use parquet::arrow::ArrowWriter;
-use parquet::file::properties::{WriterProperties, EnabledStatistics};
 use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
 use parquet::schema::types::ColumnPath;
+use std::fmt;
 use std::fs::File;
+use std::str::FromStr;
 use std::sync::Arc;
 
-pub enum BloomFilterMode {
-    Enabled,
-    Disabled,
+/// Key prefix under which per-column distinct-count estimates are stored in the
+/// file's key/value metadata, as `distinct_count:<column>` -> count.
+///
+/// This is a deliberate deviation from writing into `Statistics::distinct_count`
+/// proper: `ArrowWriter` computes column `Statistics` itself from the values it
+/// writes (min/max/null_count/row_count) and exposes no hook to inject a
+/// precomputed distinct count into that struct, so a generic Parquet reader
+/// won't see these counts. Only this crate's own readers, which know to look
+/// for the `distinct_count:` prefix, can surface them.
+pub const DISTINCT_COUNT_PREFIX: &str = "distinct_count:";
+
+/// The five event types `generate_sample_data` draws from; `event`'s bit-seen
+/// count needs each string's ordinal within this fixed domain.
+const EVENT_TYPES: [&str; 5] = ["page_view", "click", "signup", "login", "purchase"];
+
+/// Estimate the distinct-value count of every column, using the bit-seen trick
+/// for the low-cardinality enum-like columns and HyperLogLog for the
+/// high-cardinality ones, and return them as key/value metadata entries.
+fn estimate_distinct_counts(data: &AnalyticsData) -> Vec<KeyValue> {
+    let mut team_id_card = BitsetCardinality::new();
+    let mut event_card = BitsetCardinality::new();
+    let mut timestamp_card = HyperLogLog::new(14);
+    let mut distinct_id_card = HyperLogLog::new(14);
+    let mut properties_card = HyperLogLog::new(14);
+
+    for team_id in &data.team_ids {
+        // team_id is generated from 1..=10, so 0-index it into the bitset.
+        team_id_card.observe((*team_id - 1) as usize);
+    }
+    for timestamp in &data.timestamps {
+        timestamp_card.observe(timestamp);
+    }
+    for event in &data.events {
+        if let Some(ordinal) = EVENT_TYPES.iter().position(|e| e == event) {
+            event_card.observe(ordinal);
+        }
+    }
+    for distinct_id in &data.distinct_ids {
+        distinct_id_card.observe(distinct_id);
+    }
+    for properties in &data.properties {
+        properties_card.observe(properties);
+    }
+
+    let counts = [
+        ("team_id", team_id_card.estimate()),
+        ("timestamp", timestamp_card.estimate()),
+        ("event", event_card.estimate()),
+        ("distinct_id", distinct_id_card.estimate()),
+        ("properties", properties_card.estimate()),
+    ];
+
+    for (column, count) in &counts {
+        println!("  🔢 Estimated distinct count for '{}': {}", column, count);
+    }
+
+    counts
+        .into_iter()
+        .map(|(column, count)| {
+            KeyValue::new(format!("{}{}", DISTINCT_COUNT_PREFIX, column), count.to_string())
+        })
+        .collect()
+}
+
+/// A Bloom filter request for a single column, parsed from `NAME:NDV:FPP`
+/// (e.g. `event:5:0.01`).
+#[derive(Debug, Clone)]
+pub struct BloomColumnConfig {
+    pub column: String,
+    pub ndv: u64,
+    pub fpp: f64,
+}
+
+impl FromStr for BloomColumnConfig {
+    type Err = BloomColumnConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+        let [column, ndv, fpp] = parts.as_slice() else {
+            return Err(BloomColumnConfigError(format!(
+                "expected NAME:NDV:FPP, got '{}'",
+                s
+            )));
+        };
+        let ndv: u64 = ndv
+            .parse()
+            .map_err(|_| BloomColumnConfigError(format!("invalid NDV '{}' in '{}'", ndv, s)))?;
+        let fpp: f64 = fpp
+            .parse()
+            .map_err(|_| BloomColumnConfigError(format!("invalid FPP '{}' in '{}'", fpp, s)))?;
+        Ok(BloomColumnConfig {
+            column: column.to_string(),
+            ndv,
+            fpp,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BloomColumnConfigError(String);
+
+impl fmt::Display for BloomColumnConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for BloomColumnConfigError {}
+
+/// Validate that every requested Bloom-filter column exists in `schema` and is a
+/// type the byte-array Bloom filter encoder actually populates (Utf8/Binary).
+pub(crate) fn validate_bloom_columns(
+    schema: &arrow::datatypes::Schema,
+    bloom_columns: &[BloomColumnConfig],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for config in bloom_columns {
+        let field = schema
+            .field_with_name(&config.column)
+            .map_err(|_| format!("bloom column '{}' is not in the schema", config.column))?;
+        match field.data_type() {
+            DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary => {}
+            other => {
+                return Err(format!(
+                    "bloom column '{}' has type {:?}, but Bloom filters are only written for Utf8/Binary columns",
+                    config.column, other
+                )
+                .into())
+            }
+        }
+    }
+    Ok(())
 }
 
-/// Write analytics data to Parquet file with configurable Bloom filter settings
+/// Apply `bloom_columns` on top of a `WriterProperties` builder, printing a line
+/// per enabled column so callers don't have to duplicate this bookkeeping.
+pub(crate) fn apply_bloom_filters(
+    mut builder: parquet::file::properties::WriterPropertiesBuilder,
+    bloom_columns: &[BloomColumnConfig],
+) -> parquet::file::properties::WriterPropertiesBuilder {
+    if bloom_columns.is_empty() {
+        println!("  ❌ Bloom filters DISABLED");
+    }
+    for config in bloom_columns {
+        println!(
+            "  ✅ Bloom filter ENABLED for '{}' column (ndv: {}, fpp: {})",
+            config.column, config.ndv, config.fpp
+        );
+        let cp = ColumnPath::from(config.column.as_str());
+        builder = builder
+            .set_column_bloom_filter_enabled(cp.clone(), true)
+            .set_column_bloom_filter_ndv(cp.clone(), config.ndv)
+            .set_column_bloom_filter_fpp(cp, config.fpp);
+    }
+    builder
+}
+
+/// Write analytics data to a Parquet file, enabling a Bloom filter per entry in
+/// `bloom_columns` with its own NDV/FPP instead of one hardcoded column. When
+/// `build_topic_index` is set, a fixed-size Bloom index over the `properties`
+/// JSON keys/values is also built and stored in the file's key/value metadata.
 pub fn write_parquet_file(
-    data: &AnalyticsData, 
-    filename: &str, 
-    bloom_filter_mode: BloomFilterMode
+    data: &AnalyticsData,
+    filename: &str,
+    bloom_columns: &[BloomColumnConfig],
+    build_topic_index: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    
     println!("Creating Arrow schema and record batch...");
     let record_batch = data.to_record_batch()?;
     let schema = AnalyticsData::get_schema();
-    
+
+    validate_bloom_columns(&schema, bloom_columns)?;
+
     println!("Configuring Parquet writer...");
-    
-    // Configure writer properties based on Bloom filter mode
-    let props = match bloom_filter_mode {
-        BloomFilterMode::Enabled => {
-            println!("  ✅ Bloom filters ENABLED for distinct_id column");
-            let cp = ColumnPath::from("distinct_id");
-            WriterProperties::builder()
-                .set_compression(Compression::ZSTD(ZstdLevel::try_new(9).unwrap()))
-                .set_statistics_enabled(EnabledStatistics::Page)
-                .set_bloom_filter_enabled(false)  // Disable global Bloom filters
-                .set_column_bloom_filter_enabled(cp.clone(), true)
-                .set_column_bloom_filter_fpp(cp, 0.1)// Enable only for distinct_id
-                .build()
-        }
-        BloomFilterMode::Disabled => {
-            println!("  ❌ Bloom filters DISABLED");
-            WriterProperties::builder()
-                .set_compression(Compression::ZSTD(ZstdLevel::try_new(9).unwrap()))
-                .set_statistics_enabled(EnabledStatistics::Page)
-                .set_bloom_filter_enabled(false)  // Explicitly disable all Bloom filters
-                .build()
-        }
-    };
-    
+
+    // Page-level statistics (set globally below) also carry SizeStatistics
+    // (unencoded byte-array size, rep/def level histograms), which matters most
+    // for estimating the decoded size of the `properties` JSON blobs.
+    let mut builder = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::try_new(9).unwrap()))
+        .set_statistics_enabled(EnabledStatistics::Page)
+        .set_bloom_filter_enabled(false); // disable the global default; enable per-column below
+
+    let mut key_value_metadata = estimate_distinct_counts(data);
+
+    if build_topic_index {
+        let topic_index = TopicIndex::build(&data.properties);
+        println!(
+            "  🗂️  Topic Bloom index built over properties keys/values ({} bits set)",
+            topic_index.bits_set()
+        );
+        key_value_metadata.push(topic_index.to_key_value());
+    }
+    builder = builder.set_key_value_metadata(Some(key_value_metadata));
+
+    let props = apply_bloom_filters(builder, bloom_columns).build();
+
     // Create output file
     let file = File::create(filename)?;
-    
+
     println!("Writing Parquet file...");
-    
+
     // Create Arrow writer with configured properties
     let mut writer = ArrowWriter::try_new(file, Arc::new(schema), Some(props))?;
-    
+
     // Write the record batch
     writer.write(&record_batch)?;
-    
+
     // Close writer
     writer.close()?;
-    
-    let bloom_status = match bloom_filter_mode {
-        BloomFilterMode::Enabled => "WITH Bloom filters",
-        BloomFilterMode::Disabled => "WITHOUT Bloom filters",
+
+    let bloom_status = if bloom_columns.is_empty() {
+        "WITHOUT Bloom filters".to_string()
+    } else {
+        let columns: Vec<&str> = bloom_columns.iter().map(|c| c.column.as_str()).collect();
+        format!("WITH Bloom filters on [{}]", columns.join(", "))
     };
-    
-    println!("✅ Created {} with {} rows ({})", 
-             filename, 
-             data.team_ids.len(), 
-             bloom_status);
-    
+
+    println!(
+        "✅ Created {} with {} rows ({})",
+        filename,
+        data.team_ids.len(),
+        bloom_status
+    );
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,131 @@
+use crate::topic_index::{TopicIndex, METADATA_KEY};
+use arrow::array::{Array, StringArray};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use std::fs::File;
+
+/// Consult the file's topic Bloom index (if any) for whether any row's
+/// `properties` blob ever mentioned `key`, without touching a row group.
+pub fn check_has_property(filename: &str, key: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(filename)?;
+    let reader = SerializedFileReader::new(file)?;
+    let metadata = reader.metadata();
+
+    let encoded = metadata
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|kvs| kvs.iter().find(|kv| kv.key == METADATA_KEY))
+        .and_then(|kv| kv.value.clone());
+
+    let Some(encoded) = encoded else {
+        println!("⚠️  {} has no topic Bloom index; cannot pre-filter on '{}'", filename, key);
+        return Ok(());
+    };
+
+    let topic_index = TopicIndex::from_hex(&encoded)?;
+    if topic_index.has_property(key) {
+        println!("✅ Topic index: '{}' may be present somewhere in {}", key, filename);
+    } else {
+        println!("❌ Topic index: '{}' is provably absent from every row's properties", key);
+    }
+
+    Ok(())
+}
+
+/// Whether a row group's Bloom filter allows `value` to possibly be present.
+struct RowGroupVerdict {
+    row_group: usize,
+    maybe_present: bool,
+}
+
+/// Probe a Parquet file for `value` in the `distinct_id` column, using each row
+/// group's Bloom filter to decide which row groups are worth scanning at all.
+pub fn probe_distinct_id(filename: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔍 Probing {} for distinct_id = '{}'...", filename, value);
+
+    let file = File::open(filename)?;
+    let reader = SerializedFileReader::new(file)?;
+    let metadata = reader.metadata();
+
+    if metadata.num_row_groups() == 0 {
+        println!("📦 {} has no row groups; nothing to probe", filename);
+        return Ok(());
+    }
+
+    let column_idx = find_column_index(&reader, "distinct_id")?;
+
+    let mut verdicts = Vec::with_capacity(metadata.num_row_groups());
+    for rg_idx in 0..metadata.num_row_groups() {
+        let row_group_reader = reader.get_row_group(rg_idx)?;
+        let maybe_present = match row_group_reader.get_column_bloom_filter(column_idx) {
+            Some(bloom_filter) => bloom_filter.check(value.as_bytes()),
+            None => true, // no Bloom filter recorded for this row group, can't prune it
+        };
+        println!(
+            "  Row group {}: {}",
+            rg_idx,
+            if maybe_present { "maybe present" } else { "❌ provably absent, pruned" }
+        );
+        verdicts.push(RowGroupVerdict { row_group: rg_idx, maybe_present });
+    }
+
+    let surviving: Vec<usize> = verdicts
+        .iter()
+        .filter(|v| v.maybe_present)
+        .map(|v| v.row_group)
+        .collect();
+    let pruned = metadata.num_row_groups() - surviving.len();
+
+    println!(
+        "📦 {} row group(s) total, {} pruned by Bloom filter, {} need scanning",
+        metadata.num_row_groups(),
+        pruned,
+        surviving.len()
+    );
+
+    if surviving.is_empty() {
+        println!("❌ Bloom filter(s) prove '{}' is absent from every row group", value);
+        return Ok(());
+    }
+
+    let file = File::open(filename)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file)?.with_row_groups(surviving);
+    let batch_reader = builder.build()?;
+
+    let mut found = false;
+    for batch_result in batch_reader {
+        let batch = batch_result?;
+        if let Some(distinct_id_array) = batch
+            .column_by_name("distinct_id")
+            .and_then(|c| c.as_any().downcast_ref::<StringArray>())
+        {
+            if distinct_id_array.iter().flatten().any(|v| v == value) {
+                found = true;
+                break;
+            }
+        }
+    }
+
+    if found {
+        println!("✅ Confirmed: '{}' is present in the surviving row groups", value);
+    } else {
+        println!(
+            "⚠️  Bloom filter(s) let '{}' through but it was not found after scanning (false positive)",
+            value
+        );
+    }
+
+    Ok(())
+}
+
+/// Look up `name`'s column index from the file schema (not a row group's own
+/// column list), so this works even on a file with zero row groups.
+fn find_column_index(
+    reader: &SerializedFileReader<File>,
+    name: &str,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let schema = reader.metadata().file_metadata().schema_descr();
+    (0..schema.num_columns())
+        .find(|&i| schema.column(i).path().string() == name)
+        .ok_or_else(|| format!("column '{}' not found in schema", name).into())
+}
@@ -0,0 +1,79 @@
+use crate::parquet_writer::{apply_bloom_filters, validate_bloom_columns, BloomColumnConfig};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use std::fs::File;
+
+/// Parse a `--compression` value into a Parquet `Compression` codec.
+pub fn parse_compression(s: &str) -> Result<Compression, String> {
+    match s.to_lowercase().as_str() {
+        "uncompressed" | "none" => Ok(Compression::UNCOMPRESSED),
+        "snappy" => Ok(Compression::SNAPPY),
+        "gzip" => Ok(Compression::GZIP(Default::default())),
+        "lz4" => Ok(Compression::LZ4),
+        "zstd" => Ok(Compression::ZSTD(
+            parquet::basic::ZstdLevel::try_new(9).map_err(|e| e.to_string())?,
+        )),
+        other => Err(format!(
+            "unknown compression codec '{}' (expected uncompressed, snappy, gzip, lz4 or zstd)",
+            other
+        )),
+    }
+}
+
+/// Parse a `--statistics` value into a Parquet `EnabledStatistics` level.
+pub fn parse_statistics(s: &str) -> Result<EnabledStatistics, String> {
+    match s.to_lowercase().as_str() {
+        "none" => Ok(EnabledStatistics::None),
+        "chunk" => Ok(EnabledStatistics::Chunk),
+        "page" => Ok(EnabledStatistics::Page),
+        other => Err(format!(
+            "unknown statistics level '{}' (expected none, chunk or page)",
+            other
+        )),
+    }
+}
+
+/// Re-encode an existing Parquet file with new compression, statistics and
+/// Bloom-filter settings, preserving the Arrow schema read from `input`.
+pub fn rewrite_parquet_file(
+    input: &str,
+    output: &str,
+    compression: Compression,
+    statistics: EnabledStatistics,
+    bloom_columns: &[BloomColumnConfig],
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("Opening {} for rewrite...", input);
+    let file = File::open(input)?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+    let schema = builder.schema().clone();
+
+    validate_bloom_columns(&schema, bloom_columns)?;
+
+    println!("Configuring new writer properties...");
+    let props_builder = WriterProperties::builder()
+        .set_compression(compression)
+        .set_statistics_enabled(statistics)
+        .set_bloom_filter_enabled(false);
+    let props = apply_bloom_filters(props_builder, bloom_columns).build();
+
+    let reader = builder.build()?;
+    let out_file = File::create(output)?;
+    let mut writer = ArrowWriter::try_new(out_file, schema, Some(props))?;
+
+    let mut rows_written = 0usize;
+    for batch_result in reader {
+        let batch = batch_result?;
+        rows_written += batch.num_rows();
+        writer.write(&batch)?;
+    }
+    writer.close()?;
+
+    println!(
+        "✅ Rewrote {} ({} rows) to {} with {:?} compression",
+        input, rows_written, output, compression
+    );
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use crate::data_generator::AnalyticsData;
+use arrow::array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray, UInt64Array};
+use arrow::datatypes::{DataType, TimeUnit};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::statistics::Statistics;
+use std::fs::File;
+use std::sync::Arc;
+
+/// Row-group-granularity statistics for one column, materialized as Arrow
+/// arrays (one element per row group) instead of the ad-hoc little-endian byte
+/// decoding `map_parquet` does inline.
+pub struct RowGroupStatistics {
+    pub min: ArrayRef,
+    pub max: ArrayRef,
+    pub null_count: UInt64Array,
+    pub row_count: UInt64Array,
+}
+
+/// Materialize per-row-group min/max/null_count/row_count for `column_name`,
+/// typed according to the column's Arrow `DataType` in [`AnalyticsData::get_schema`].
+pub fn column_statistics(
+    reader: &SerializedFileReader<File>,
+    column_name: &str,
+) -> Result<RowGroupStatistics, Box<dyn std::error::Error>> {
+    let schema = AnalyticsData::get_schema();
+    let data_type = schema
+        .field_with_name(column_name)
+        .map_err(|_| format!("column '{}' is not in the schema", column_name))?
+        .data_type()
+        .clone();
+
+    let metadata = reader.metadata();
+    if metadata.num_row_groups() == 0 {
+        let empty: ArrayRef = match &data_type {
+            DataType::Int64 => Arc::new(Int64Array::from(Vec::<i64>::new())),
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                Arc::new(TimestampNanosecondArray::from(Vec::<i64>::new()))
+            }
+            DataType::Utf8 => Arc::new(StringArray::from(Vec::<&str>::new())),
+            other => return Err(format!("unsupported column type {:?}", other).into()),
+        };
+        return Ok(RowGroupStatistics {
+            min: empty.clone(),
+            max: empty,
+            null_count: UInt64Array::from(Vec::<u64>::new()),
+            row_count: UInt64Array::from(Vec::<u64>::new()),
+        });
+    }
+
+    let column_idx = (0..metadata.row_group(0).num_columns())
+        .find(|&i| metadata.row_group(0).column(i).column_path().string() == column_name)
+        .ok_or_else(|| format!("column '{}' not found in row group metadata", column_name))?;
+
+    let mut null_counts = Vec::with_capacity(metadata.num_row_groups());
+    let mut row_counts = Vec::with_capacity(metadata.num_row_groups());
+    let mut i64_min: Vec<Option<i64>> = Vec::new();
+    let mut i64_max: Vec<Option<i64>> = Vec::new();
+    let mut str_min: Vec<Option<String>> = Vec::new();
+    let mut str_max: Vec<Option<String>> = Vec::new();
+
+    for rg_idx in 0..metadata.num_row_groups() {
+        let row_group = metadata.row_group(rg_idx);
+        let column = row_group.column(column_idx);
+        row_counts.push(row_group.num_rows() as u64);
+
+        let stats = column.statistics();
+        null_counts.push(stats.and_then(|s| s.null_count_opt()).unwrap_or(0));
+
+        match &data_type {
+            DataType::Int64 | DataType::Timestamp(_, _) => {
+                let (min, max) = match stats {
+                    Some(Statistics::Int64(s)) if s.min_is_exact() && s.max_is_exact() => {
+                        (s.min_opt().copied(), s.max_opt().copied())
+                    }
+                    _ => (None, None),
+                };
+                i64_min.push(min);
+                i64_max.push(max);
+            }
+            DataType::Utf8 => {
+                let (min, max) = match stats {
+                    Some(Statistics::ByteArray(s)) if s.min_is_exact() && s.max_is_exact() => (
+                        s.min_bytes_opt().map(|b| String::from_utf8_lossy(b).to_string()),
+                        s.max_bytes_opt().map(|b| String::from_utf8_lossy(b).to_string()),
+                    ),
+                    _ => (None, None),
+                };
+                str_min.push(min);
+                str_max.push(max);
+            }
+            other => {
+                return Err(format!(
+                    "unsupported column type {:?} for statistics conversion",
+                    other
+                )
+                .into())
+            }
+        }
+    }
+
+    let (min, max): (ArrayRef, ArrayRef) = match &data_type {
+        DataType::Int64 => (
+            Arc::new(Int64Array::from(i64_min)),
+            Arc::new(Int64Array::from(i64_max)),
+        ),
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => (
+            Arc::new(TimestampNanosecondArray::from(i64_min)),
+            Arc::new(TimestampNanosecondArray::from(i64_max)),
+        ),
+        DataType::Utf8 => (
+            Arc::new(StringArray::from(str_min)),
+            Arc::new(StringArray::from(str_max)),
+        ),
+        other => return Err(format!("unsupported column type {:?}", other).into()),
+    };
+
+    Ok(RowGroupStatistics {
+        min,
+        max,
+        null_count: UInt64Array::from(null_counts),
+        row_count: UInt64Array::from(row_counts),
+    })
+}
+
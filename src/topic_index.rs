@@ -0,0 +1,119 @@
+use parquet::file::metadata::KeyValue;
+use std::hash::{Hash, Hasher};
+use twox_hash::XxHash64;
+
+/// Key under which the encoded topic index is stored in the file's key/value metadata.
+pub const METADATA_KEY: &str = "topic_bloom_index";
+
+const NUM_BITS: usize = 2048;
+const NUM_BYTES: usize = NUM_BITS / 8;
+const BIT_MASK: usize = NUM_BITS - 1; // 2048 is a power of two, so this is 11 ones
+
+/// A fixed-width, 2048-bit Bloom filter over tokens derived from the JSON
+/// `properties` column (its keys and their low-cardinality values), so `Probe`
+/// can answer "does any row mention this key?" without touching a row group.
+pub struct TopicIndex {
+    bits: [u8; NUM_BYTES],
+}
+
+impl TopicIndex {
+    fn empty() -> Self {
+        TopicIndex { bits: [0u8; NUM_BYTES] }
+    }
+
+    /// Build the index over every key and key=value pair found in `properties`,
+    /// one JSON blob per row.
+    pub fn build(properties: &[String]) -> Self {
+        let mut index = Self::empty();
+        for raw in properties {
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(raw) else {
+                continue;
+            };
+            let Some(object) = value.as_object() else {
+                continue;
+            };
+            for (key, value) in object {
+                index.insert(&format!("key:{}", key));
+                if let Some(scalar) = scalar_string(value) {
+                    index.insert(&format!("value:{}={}", key, scalar));
+                }
+            }
+        }
+        index
+    }
+
+    fn insert(&mut self, token: &str) {
+        for bit in bit_positions(token) {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Does the index indicate a row may contain `token` ("key:NAME" or
+    /// "value:NAME=VALUE")? False means provably absent.
+    pub fn check(&self, token: &str) -> bool {
+        bit_positions(token).into_iter().all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Does the index indicate any row has a `properties` blob containing `key`?
+    pub fn has_property(&self, key: &str) -> bool {
+        self.check(&format!("key:{}", key))
+    }
+
+    pub fn bits_set(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Encode as a hex string suitable for storing as Parquet key/value metadata.
+    pub fn to_hex(&self) -> String {
+        self.bits.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Decode from the hex string produced by [`TopicIndex::to_hex`].
+    pub fn from_hex(hex: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        if hex.len() != NUM_BYTES * 2 {
+            return Err(format!(
+                "expected {} hex characters for a {}-bit topic index, got {}",
+                NUM_BYTES * 2,
+                NUM_BITS,
+                hex.len()
+            )
+            .into());
+        }
+        let mut bits = [0u8; NUM_BYTES];
+        for (i, byte) in bits.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)?;
+        }
+        Ok(TopicIndex { bits })
+    }
+
+    /// Wrap the encoded index as a Parquet `KeyValue` ready to attach to
+    /// `WriterProperties::set_key_value_metadata`.
+    pub fn to_key_value(&self) -> KeyValue {
+        KeyValue::new(METADATA_KEY.to_string(), self.to_hex())
+    }
+}
+
+/// Render a JSON scalar as a short token string; objects/arrays are skipped since
+/// they aren't the low-cardinality values this index targets.
+fn scalar_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Derive three bit positions from three distinct byte-pairs of the xxhash64
+/// digest of `token`, each masked into the 2048-bit space.
+fn bit_positions(token: &str) -> [usize; 3] {
+    let mut hasher = XxHash64::with_seed(0);
+    token.hash(&mut hasher);
+    let digest = hasher.finish().to_le_bytes();
+
+    [
+        u16::from_le_bytes([digest[0], digest[1]]) as usize & BIT_MASK,
+        u16::from_le_bytes([digest[2], digest[3]]) as usize & BIT_MASK,
+        u16::from_le_bytes([digest[4], digest[5]]) as usize & BIT_MASK,
+    ]
+}
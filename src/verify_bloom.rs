@@ -1,21 +1,38 @@
+use crate::topic_index::{TopicIndex, METADATA_KEY};
 use parquet::file::reader::{FileReader, SerializedFileReader};
 use std::fs::File;
 
 pub fn verify_bloom_filter(filename: &str) -> Result<(), Box<dyn std::error::Error>> {
     println!("Verifying Bloom filters in {}...", filename);
-    
+
     // Open the parquet file
     let file = File::open(filename)?;
     let reader = SerializedFileReader::new(file)?;
-    
+
     // Get file metadata
     let metadata = reader.metadata();
-    
+
     println!("Parquet file metadata:");
     println!("Version: {}", metadata.file_metadata().version());
     println!("Number of rows: {}", metadata.file_metadata().num_rows());
     println!("Number of row groups: {}", metadata.num_row_groups());
-    
+
+    let topic_index = metadata
+        .file_metadata()
+        .key_value_metadata()
+        .and_then(|kvs| kvs.iter().find(|kv| kv.key == METADATA_KEY))
+        .and_then(|kv| kv.value.clone());
+    match topic_index {
+        Some(encoded) => match TopicIndex::from_hex(&encoded) {
+            Ok(topic_index) => println!(
+                "🗂️  Topic Bloom index present ({} bits set)",
+                topic_index.bits_set()
+            ),
+            Err(e) => println!("⚠️  Topic Bloom index present but could not be decoded: {}", e),
+        },
+        None => println!("No topic Bloom index in file metadata"),
+    }
+
     // Check each row group for Bloom filters
     for i in 0..metadata.num_row_groups() {
         let row_group = metadata.row_group(i);